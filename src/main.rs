@@ -1,31 +1,132 @@
 use std::error::Error;
 use std::env;
-use tokio_postgres::{NoTls, Row};
+use std::fs;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine as _};
+use openssl::ssl::{SslConnector, SslFiletype, SslMethod, SslVerifyMode};
+use postgres_openssl::MakeTlsConnector;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::mpsc;
+use tokio_postgres::config::{Config as PgConfig, TargetSessionAttrs};
+use tokio_postgres::{AsyncMessage, Client, Connection, NoTls};
+use futures_util::{stream, StreamExt};
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Direction, Layout},
     style::{Color, Style},
     text::Span,
-    widgets::{Block, Borders, Paragraph, Table, Row as TableRow},
+    widgets::{Block, Borders, Paragraph, Table, TableState, Row as TableRow},
     Terminal,
 };
 use crossterm::{event, terminal::{enable_raw_mode, disable_raw_mode}};
 use std::io::stdout;
 use tokio::time::{self, Duration};
+use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod};
+
+mod stats;
+use stats::View;
+
+// Columns the user can sort by, cycled with `s` (number keys switch views).
+// The SQL identifier is fixed here so it can be interpolated into ORDER BY
+// without risking injection from user input.
+const SORT_COLUMNS: &[(&str, &str)] = &[
+    ("Tuples Inserted", "n_tup_ins"),
+    ("Tuples Updated", "n_tup_upd"),
+    ("Tuples Deleted", "n_tup_del"),
+    ("Hot Updates", "n_tup_hot_upd"),
+    ("Live Tuples", "n_live_tup"),
+    ("Dead Tuples", "n_dead_tup"),
+    ("Index Fetch", "idx_tup_fetch"),
+];
+
+// Interactive state for the dashboard: selection, sort column, relname/
+// schemaname filter, whether the filter is being typed, and pause.
+struct AppState {
+    table_state: TableState,
+    view: View,
+    sort: usize,
+    filter: String,
+    editing_filter: bool,
+    paused: bool,
+    reconnecting: bool,
+    notifications: Vec<String>,
+    tables: Vec<Vec<String>>,
+    activity: Vec<Vec<String>>,
+    database: Vec<Vec<String>>,
+}
+
+// Newest notification payloads kept for the side panel; older ones are dropped.
+const MAX_NOTIFICATIONS: usize = 10;
+
+impl AppState {
+    fn new() -> Self {
+        let mut table_state = TableState::default();
+        table_state.select(Some(0));
+        AppState {
+            table_state,
+            view: View::Tables,
+            sort: 0,
+            filter: String::new(),
+            editing_filter: false,
+            paused: false,
+            reconnecting: false,
+            notifications: Vec::new(),
+            tables: Vec::new(),
+            activity: Vec::new(),
+            database: Vec::new(),
+        }
+    }
+
+    // Rows backing the currently-selected view.
+    fn rows(&self) -> &[Vec<String>] {
+        match self.view {
+            View::Tables => &self.tables,
+            View::Activity => &self.activity,
+            View::Database => &self.database,
+        }
+    }
+
+    // Record a NOTIFY payload for the side panel, keeping only the most recent.
+    fn push_notification(&mut self, payload: String) {
+        self.notifications.push(payload);
+        if self.notifications.len() > MAX_NOTIFICATIONS {
+            let overflow = self.notifications.len() - MAX_NOTIFICATIONS;
+            self.notifications.drain(0..overflow);
+        }
+    }
+
+    fn sort_column(&self) -> &'static str {
+        SORT_COLUMNS[self.sort].1
+    }
+
+    // Move the selection, clamping to the row count.
+    fn move_selection(&mut self, delta: isize, len: usize) {
+        if len == 0 {
+            self.table_state.select(None);
+            return;
+        }
+        let current = self.table_state.selected().unwrap_or(0) as isize;
+        let next = (current + delta).clamp(0, len as isize - 1);
+        self.table_state.select(Some(next as usize));
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
-    // 1. Read DATABASE_URL from environment variables
-    let database_url = env::var("DATABASE_URL")
-        .expect("DATABASE_URL must be set in environment variables");
+    // 1. Parse the connection configuration from the environment. Missing or
+    // malformed configuration returns a clear error instead of panicking.
+    let config = load_config()?;
 
-    // 2. Connect to PostgreSQL
-    let (client, connection) = tokio_postgres::connect(&database_url, NoTls).await?;
-    tokio::spawn(async move {
-        if let Err(e) = connection.await {
-            eprintln!("connection error: {}", e);
-        }
-    });
+    // 2. Connect to PostgreSQL (optionally over TLS, driven by PG_SSL* env vars).
+    // The primary client drives LISTEN/NOTIFY; a pool serves the per-view
+    // queries so they can run concurrently each refresh.
+    let (mut client, mut notifications) = connect(&config).await?;
+    let pool = build_pool(&config)?;
+
+    // Optionally subscribe to a NOTIFY channel for push-based refreshes.
+    let notify_channel = env::var("PG_NOTIFY_CHANNEL").ok().filter(|c| !c.is_empty());
+    if let Some(channel) = &notify_channel {
+        listen(&client, channel).await?;
+    }
 
     // 3. Initialize terminal UI
     let mut stdout = stdout();
@@ -33,97 +134,427 @@ async fn main() -> Result<(), Box<dyn Error>> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    // 4. Main event loop
-    let mut interval = time::interval(Duration::from_secs(2));
+    // 4. Main event loop. We `select!` between the refresh interval, incoming
+    // NOTIFY messages, and terminal input, so a DB event (e.g. after a bulk
+    // load) refreshes the table immediately rather than waiting for the tick.
+    let mut refresh = time::interval(Duration::from_secs(2));
+    let mut events = event::EventStream::new();
+    let mut app = AppState::new();
+    let mut due = true;
     loop {
-        let rows = fetch_pg_stat_all_tables(&client).await?;
-        terminal.draw(|f| {
-            let size = f.area();
-
-            // Layout
-            let chunks = Layout::default()
-                .direction(Direction::Vertical)
-                .constraints(
-                    [
-                        Constraint::Percentage(10),
-                        Constraint::Percentage(90),
-                    ]
-                    .as_ref(),
-                )
-                .split(size);
-
-            // Title
-            let title = Paragraph::new("PostgreSQL Table Statistics Monitor").block(
+        // Refresh every view concurrently when due and not paused. A dropped
+        // connection must not tear down the terminal: transient failures
+        // trigger a backoff reconnect (the pool self-heals) while the last good
+        // snapshot stays on screen, and only genuine fatal errors (bad
+        // credentials / config) abort.
+        if due && !app.paused {
+            let fetched = tokio::try_join!(
+                stats::fetch_tables(&pool, app.sort_column(), &app.filter),
+                stats::fetch_activity(&pool),
+                stats::fetch_database(&pool),
+            );
+            match fetched {
+                Ok((tables, activity, database)) => {
+                    app.tables = tables;
+                    app.activity = activity;
+                    app.database = database;
+                    let len = app.rows().len();
+                    app.move_selection(0, len);
+                }
+                Err(e) if is_fatal(&e) => return Err(e),
+                Err(_) => {
+                    let (new_client, new_notifications) =
+                        reconnect(&mut terminal, &config, &mut app).await?;
+                    client = new_client;
+                    notifications = new_notifications;
+                    if let Some(channel) = &notify_channel {
+                        listen(&client, channel).await?;
+                    }
+                }
+            }
+            due = false;
+        }
+
+        draw_dashboard(&mut terminal, &mut app)?;
+
+        tokio::select! {
+            _ = refresh.tick() => due = true,
+            Some(payload) = notifications.recv() => {
+                app.push_notification(payload);
+                due = true;
+            }
+            Some(Ok(event::Event::Key(key))) = events.next() => {
+                let len = app.rows().len();
+                if handle_key(&mut app, key, len) {
+                    break;
+                }
+                // A sort, filter, or view change must re-query immediately.
+                due = true;
+            }
+        }
+    }
+
+    disable_raw_mode()?;
+    Ok(())
+}
+
+// Parse the connection configuration from DATABASE_URL into a
+// `tokio_postgres::Config`, which natively supports the standard libpq options
+// — comma-separated `host` entries with per-host failover, `hostaddr` to skip
+// DNS, `connect_timeout`, and `target_session_attrs`. PG_CONNECT_TIMEOUT and
+// PG_TARGET_SESSION_ATTRS override the parsed values so an HA cluster can be
+// steered at the writable primary without editing the URL. Errors are
+// returned rather than panicked so misconfiguration is reported cleanly.
+fn load_config() -> Result<PgConfig, Box<dyn Error>> {
+    let database_url =
+        env::var("DATABASE_URL").map_err(|_| "DATABASE_URL must be set in environment variables")?;
+    let mut config: PgConfig = database_url
+        .parse()
+        .map_err(|e| format!("invalid DATABASE_URL: {}", e))?;
+
+    if let Ok(secs) = env::var("PG_CONNECT_TIMEOUT") {
+        let secs: u64 = secs
+            .parse()
+            .map_err(|_| "PG_CONNECT_TIMEOUT must be a number of seconds")?;
+        config.connect_timeout(Duration::from_secs(secs));
+    }
+    if let Ok(attrs) = env::var("PG_TARGET_SESSION_ATTRS") {
+        let target = match attrs.as_str() {
+            "any" => TargetSessionAttrs::Any,
+            "read-write" => TargetSessionAttrs::ReadWrite,
+            other => return Err(format!("unsupported PG_TARGET_SESSION_ATTRS: {}", other).into()),
+        };
+        config.target_session_attrs(target);
+    }
+
+    Ok(config)
+}
+
+// Build a connection pool for the per-view queries. The pool size is taken
+// from PG_MAX_POOL_CONNS (default 4) and TLS mirrors the primary connection.
+fn build_pool(config: &PgConfig) -> Result<Pool, Box<dyn Error>> {
+    let mgr_config = ManagerConfig {
+        recycling_method: RecyclingMethod::Fast,
+    };
+    let max_size = env::var("PG_MAX_POOL_CONNS")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok())
+        .unwrap_or(4);
+
+    let manager = match tls_connector()? {
+        None => Manager::from_config(config.clone(), NoTls, mgr_config),
+        Some(connector) => Manager::from_config(config.clone(), connector, mgr_config),
+    };
+    let pool = Pool::builder(manager).max_size(max_size).build()?;
+    Ok(pool)
+}
+
+// Apply a key press to the app state. Returns true when the user asked to
+// quit. While editing the filter, printable keys build the filter string;
+// otherwise keys drive scrolling, sorting, and pause.
+fn handle_key(app: &mut AppState, key: event::KeyEvent, len: usize) -> bool {
+    use event::KeyCode;
+
+    if app.editing_filter {
+        match key.code {
+            KeyCode::Enter | KeyCode::Esc => app.editing_filter = false,
+            KeyCode::Backspace => {
+                app.filter.pop();
+            }
+            KeyCode::Char(c) => app.filter.push(c),
+            _ => {}
+        }
+        return false;
+    }
+
+    match key.code {
+        KeyCode::Char('q') => return true,
+        KeyCode::Char(' ') => app.paused = !app.paused,
+        KeyCode::Char('/') => {
+            app.filter.clear();
+            app.editing_filter = true;
+        }
+        KeyCode::Char('s') => app.sort = (app.sort + 1) % SORT_COLUMNS.len(),
+        KeyCode::Tab => {
+            app.view = app.view.next();
+            app.table_state.select(Some(0));
+        }
+        KeyCode::Char(c @ '1'..='9') => {
+            if let Some(view) = View::from_index(c as usize - '1' as usize) {
+                app.view = view;
+                app.table_state.select(Some(0));
+            }
+        }
+        KeyCode::Down | KeyCode::Char('j') => app.move_selection(1, len),
+        KeyCode::Up | KeyCode::Char('k') => app.move_selection(-1, len),
+        _ => {}
+    }
+    false
+}
+
+// Render the dashboard: a title/status block plus the stats table. The title
+// surfaces the sort column, active filter, and pause/reconnect state; the
+// table tracks the current selection via `AppState`.
+fn draw_dashboard(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    app: &mut AppState,
+) -> Result<(), Box<dyn Error>> {
+    terminal.draw(|f| {
+        let size = f.area();
+
+        // Layout
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(10),
+                    Constraint::Percentage(90),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+
+        // Title with the current controls' state.
+        let filter = if app.editing_filter {
+            format!("/{}_", app.filter)
+        } else if app.filter.is_empty() {
+            "none".to_string()
+        } else {
+            app.filter.clone()
+        };
+        let status = if app.reconnecting {
+            "reconnecting…"
+        } else if app.paused {
+            "paused"
+        } else {
+            "live"
+        };
+        let tabs = "[Tab] next  [1] Tables  [2] Activity  [3] Database";
+        let title = Paragraph::new(format!(
+            "PostgreSQL Statistics Monitor  [{}]  {}  sort: {}  filter: {}",
+            status, tabs, SORT_COLUMNS[app.sort].0, filter
+        ))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Dashboard")
+                .title_style(Style::default().fg(Color::Yellow)),
+        );
+        f.render_widget(title, chunks[0]);
+
+        // Split off a side panel for recent NOTIFY payloads once any arrive.
+        let body = if app.notifications.is_empty() {
+            chunks[1]
+        } else {
+            let cols = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(75), Constraint::Percentage(25)].as_ref())
+                .split(chunks[1]);
+            let notes = app.notifications.iter().rev().cloned().collect::<Vec<_>>().join("\n");
+            let panel = Paragraph::new(notes).block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title("Dashboard")
-                    .title_style(Style::default().fg(Color::Yellow)),
+                    .title("Notifications"),
             );
-            f.render_widget(title, chunks[0]);
+            f.render_widget(panel, cols[1]);
+            cols[0]
+        };
 
-            // Data Table with Headers
-            let header = TableRow::new(vec![
-                "Schema", "Table", "Index Fetch", "Tuples Inserted",
-                "Tuples Updated", "Tuples Deleted", "Hot Updates", "Live Tuples", "Dead Tuples",
-            ])
+        // Data table with headers, dispatched on the active view.
+        let view = app.view;
+        let header = TableRow::new(view.headers().to_vec())
             .style(Style::default().fg(Color::Green).add_modifier(ratatui::style::Modifier::BOLD));
+        let widths = view
+            .widths()
+            .iter()
+            .map(|p| Constraint::Percentage(*p))
+            .collect::<Vec<_>>();
 
-            let table = Table::new(rows, [
-                    Constraint::Percentage(15),
-                    Constraint::Percentage(15),
-                    Constraint::Percentage(10),
-                    Constraint::Percentage(10),
-                    Constraint::Percentage(10),
-                    Constraint::Percentage(10),
-                    Constraint::Percentage(10),
-                    Constraint::Percentage(10),
-                    Constraint::Percentage(10),
-                ])
-                .header(header)
-                .block(Block::default().borders(Borders::ALL).title("pg_stat_all_tables"));
-
-            f.render_widget(table, chunks[1]);
-        })?;
+        let table = Table::new(
+            app.rows().iter().map(|cells| TableRow::new(cells.clone())),
+            widths,
+        )
+        .header(header)
+        .row_highlight_style(Style::default().add_modifier(ratatui::style::Modifier::REVERSED))
+        .block(Block::default().borders(Borders::ALL).title(view.title()));
 
-        // Refresh interval
-        interval.tick().await;
+        f.render_stateful_widget(table, body, &mut app.table_state);
+    })?;
+    Ok(())
+}
 
-        // Exit condition
-        if event::poll(Duration::from_millis(200))? {
-            if let event::Event::Key(key) = event::read()? {
-                if key.code == event::KeyCode::Char('q') {
-                    break;
-                }
+// Re-establish a dropped connection with exponential backoff (250ms doubling
+// up to ~30s, with jitter). Bad credentials / config abort instead of looping;
+// everything else is treated as transient. The last snapshot stays visible
+// with a "reconnecting…" status while we retry.
+async fn reconnect(
+    terminal: &mut Terminal<CrosstermBackend<std::io::Stdout>>,
+    config: &PgConfig,
+    app: &mut AppState,
+) -> Result<(Client, mpsc::UnboundedReceiver<String>), Box<dyn Error>> {
+    let mut delay = Duration::from_millis(250);
+    let max_delay = Duration::from_secs(30);
+    app.reconnecting = true;
+    loop {
+        draw_dashboard(terminal, app)?;
+        match connect(config).await {
+            Ok(connected) => {
+                app.reconnecting = false;
+                return Ok(connected);
+            }
+            Err(e) if is_fatal_boxed(&e) => return Err(e),
+            Err(_) => {
+                time::sleep(delay + jitter(delay)).await;
+                delay = (delay * 2).min(max_delay);
             }
         }
     }
+}
 
-    disable_raw_mode()?;
+// Subscribe to a NOTIFY channel. The identifier is quoted (doubling any
+// embedded quotes) so arbitrary channel names are handled safely.
+async fn listen(client: &Client, channel: &str) -> Result<(), Box<dyn Error>> {
+    let quoted = channel.replace('"', "\"\"");
+    client.batch_execute(&format!("LISTEN \"{}\"", quoted)).await?;
     Ok(())
 }
 
-// Fetch pg_stat_all_tables data
-async fn fetch_pg_stat_all_tables(client: &tokio_postgres::Client) -> Result<Vec<TableRow>, Box<dyn Error>> {
-    let rows = client.query(
-        "SELECT schemaname, relname, idx_tup_fetch, n_tup_ins, n_tup_upd, n_tup_del, n_tup_hot_upd, n_live_tup, n_dead_tup FROM pg_stat_all_tables ORDER BY n_tup_ins desc LIMIT 15",
-        &[],
-    ).await?;
-
-    Ok(rows
-        .iter()
-        .map(|row| {
-            TableRow::new(vec![
-                row.get::<_, &str>("schemaname").to_string(),
-                row.get::<_, &str>("relname").to_string(),
-                row.get::<_, Option<i64>>("idx_tup_fetch").unwrap_or(0).to_string(),
-                row.get::<_, Option<i64>>("n_tup_ins").unwrap_or(0).to_string(),
-                row.get::<_, Option<i64>>("n_tup_upd").unwrap_or(0).to_string(),
-                row.get::<_, Option<i64>>("n_tup_del").unwrap_or(0).to_string(),
-                row.get::<_, Option<i64>>("n_tup_hot_upd").unwrap_or(0).to_string(),
-                row.get::<_, Option<i64>>("n_live_tup").unwrap_or(0).to_string(),
-                row.get::<_, Option<i64>>("n_dead_tup").unwrap_or(0).to_string(),
-            ])
-        })
-        .collect())
+// A connection is transient when it is closed or rooted in an IO error; SQL
+// errors carry a SQLSTATE and are considered fatal for reconnect purposes.
+fn is_fatal(err: &(dyn Error + 'static)) -> bool {
+    if let Some(pg) = err.downcast_ref::<tokio_postgres::Error>() {
+        if pg.is_closed() {
+            return false;
+        }
+        // A SQLSTATE means the server answered — authentication/config faults
+        // land here and must not be retried forever.
+        return pg.code().is_some();
+    }
+    // Unknown error shapes (io, config parsing, TLS) are treated as transient.
+    false
 }
 
+fn is_fatal_boxed(err: &Box<dyn Error>) -> bool {
+    is_fatal(err.as_ref())
+}
+
+// Deterministic-free jitter in the range [0, delay/2) derived from the clock,
+// spreading reconnect attempts so a fleet of monitors doesn't thunder.
+fn jitter(delay: Duration) -> Duration {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.subsec_nanos() as u64)
+        .unwrap_or(0);
+    let span = (delay.as_millis() as u64 / 2).max(1);
+    Duration::from_millis(nanos % span)
+}
+
+// Open a connection to PostgreSQL, selecting TLS based on PG_SSLMODE.
+//
+// `disable` (the default) keeps the historical `NoTls` behaviour; `require`
+// encrypts the link without validating the server certificate; `verify-full`
+// additionally validates the server against PG_CA_CERT_PATH. Client
+// authentication is enabled when PG_CLIENT_CERT_PATH / PG_CLIENT_KEY_PATH are
+// set. Each cert path may point at a PEM file or hold base64-encoded PEM.
+// `Config::connect` iterates the configured hosts in order (honouring
+// `target_session_attrs` so replicas are skipped when a writable primary is
+// required) and returns an error only when every host fails.
+async fn connect(config: &PgConfig) -> Result<(Client, mpsc::UnboundedReceiver<String>), Box<dyn Error>> {
+    match tls_connector()? {
+        None => {
+            let (client, connection) = config.connect(NoTls).await?;
+            Ok((client, spawn_connection(connection)))
+        }
+        Some(connector) => {
+            let (client, connection) = config.connect(connector).await?;
+            Ok((client, spawn_connection(connection)))
+        }
+    }
+}
+
+// Build a TLS connector from the PG_SSL* environment, or `None` for the
+// default plaintext (`disable`) mode. Shared by the primary connection and the
+// view connection pool so both honour the same SSL configuration.
+fn tls_connector() -> Result<Option<MakeTlsConnector>, Box<dyn Error>> {
+    let sslmode = env::var("PG_SSLMODE").unwrap_or_else(|_| "disable".to_string());
+    if sslmode == "disable" {
+        return Ok(None);
+    }
+
+    let mut builder = SslConnector::builder(SslMethod::tls())?;
+    match sslmode.as_str() {
+        "require" => builder.set_verify(SslVerifyMode::NONE),
+        "verify-full" => {
+            builder.set_verify(SslVerifyMode::PEER);
+            let ca = env::var("PG_CA_CERT_PATH")
+                .map_err(|_| "PG_SSLMODE=verify-full requires PG_CA_CERT_PATH")?;
+            builder.set_ca_file(materialize_pem(&ca)?)?;
+        }
+        other => return Err(format!("unsupported PG_SSLMODE: {}", other).into()),
+    }
+
+    if let Ok(cert) = env::var("PG_CLIENT_CERT_PATH") {
+        builder.set_certificate_file(materialize_pem(&cert)?, SslFiletype::PEM)?;
+        let key = env::var("PG_CLIENT_KEY_PATH")
+            .map_err(|_| "PG_CLIENT_CERT_PATH requires PG_CLIENT_KEY_PATH")?;
+        builder.set_private_key_file(materialize_pem(&key)?, SslFiletype::PEM)?;
+    }
+
+    Ok(Some(MakeTlsConnector::new(builder.build())))
+}
+
+// Drive the connection future on a background task, forwarding the payloads of
+// any `AsyncMessage::Notification` to the returned channel. Polling the
+// connection for messages (rather than just awaiting it) is what makes
+// LISTEN/NOTIFY delivery possible; callers that don't LISTEN simply never see
+// anything on the receiver.
+fn spawn_connection<S, T>(connection: Connection<S, T>) -> mpsc::UnboundedReceiver<String>
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    T: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        let mut connection = connection;
+        let mut messages = stream::poll_fn(move |cx| connection.poll_message(cx));
+        while let Some(message) = messages.next().await {
+            match message {
+                Ok(AsyncMessage::Notification(note)) => {
+                    let _ = tx.send(note.payload().to_string());
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    eprintln!("connection error: {}", e);
+                    break;
+                }
+            }
+        }
+    });
+    rx
+}
+
+// Resolve cert material to a readable PEM file path. If the variable holds a
+// path to an existing file it is used as-is; otherwise it is treated as
+// base64-encoded PEM, decoded, and written to a temp file for OpenSSL to read.
+fn materialize_pem(value: &str) -> Result<std::path::PathBuf, Box<dyn Error>> {
+    let path = std::path::Path::new(value);
+    if path.is_file() {
+        return Ok(path.to_path_buf());
+    }
+    let decoded = BASE64
+        .decode(value.trim().as_bytes())
+        .map_err(|e| format!("cert material is neither a file nor valid base64: {}", e))?;
+    let tmp = env::temp_dir().join(format!("btm-pg-{:x}.pem", fnv1a(&decoded)));
+    fs::write(&tmp, decoded)?;
+    Ok(tmp)
+}
+
+// Small stable hash so repeated runs reuse the same temp file.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for &b in bytes {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
@@ -0,0 +1,193 @@
+//! Per-view statistics queries backing the tabbed dashboard.
+//!
+//! Each view owns one query and the column metadata used to render it. The
+//! activity and database views decode rows into a typed struct; the tables
+//! view maps its columns straight to strings. Fetches take a pooled client so the main loop can run them
+//! concurrently without serialising on a single connection.
+
+use std::error::Error;
+
+use deadpool_postgres::Pool;
+
+// The dashboards selectable with Tab / 1 / 2 / 3.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum View {
+    Tables,
+    Activity,
+    Database,
+}
+
+impl View {
+    // Title shown in the table block.
+    pub fn title(self) -> &'static str {
+        match self {
+            View::Tables => "pg_stat_all_tables",
+            View::Activity => "pg_stat_activity",
+            View::Database => "pg_stat_database",
+        }
+    }
+
+    // Cycle to the next view, wrapping around.
+    pub fn next(self) -> View {
+        match self {
+            View::Tables => View::Activity,
+            View::Activity => View::Database,
+            View::Database => View::Tables,
+        }
+    }
+
+    // Select a view by 0-based index (key `1` maps to 0); out-of-range
+    // indices yield `None`.
+    pub fn from_index(index: usize) -> Option<View> {
+        match index {
+            0 => Some(View::Tables),
+            1 => Some(View::Activity),
+            2 => Some(View::Database),
+            _ => None,
+        }
+    }
+
+    // Column headers for this view.
+    pub fn headers(self) -> &'static [&'static str] {
+        match self {
+            View::Tables => &[
+                "Schema", "Table", "Index Fetch", "Tuples Inserted", "Tuples Updated",
+                "Tuples Deleted", "Hot Updates", "Live Tuples", "Dead Tuples",
+            ],
+            View::Activity => &["PID", "User", "State", "Wait Event", "Query"],
+            View::Database => &[
+                "Database", "Commits", "Rollbacks", "Blocks Read", "Blocks Hit", "Hit Ratio %",
+            ],
+        }
+    }
+
+    // Column widths as layout percentages, one per header.
+    pub fn widths(self) -> &'static [u16] {
+        match self {
+            View::Tables => &[15, 15, 10, 10, 10, 10, 10, 10, 10],
+            View::Activity => &[10, 15, 15, 20, 40],
+            View::Database => &[25, 15, 15, 15, 15, 15],
+        }
+    }
+}
+
+// Live sessions from pg_stat_activity.
+struct ActivityStat {
+    pid: i32,
+    usename: Option<String>,
+    state: Option<String>,
+    wait_event: Option<String>,
+    query: Option<String>,
+}
+
+// Per-database counters from pg_stat_database.
+struct DatabaseStat {
+    datname: Option<String>,
+    xact_commit: i64,
+    xact_rollback: i64,
+    blks_read: i64,
+    blks_hit: i64,
+}
+
+// Fetch the table statistics, honouring the interactive sort column and
+// relname/schemaname filter. `sort_column` is a whitelisted identifier.
+pub async fn fetch_tables(
+    pool: &Pool,
+    sort_column: &str,
+    filter: &str,
+) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    let client = pool.get().await?;
+    let sql = format!(
+        "SELECT schemaname, relname, idx_tup_fetch, n_tup_ins, n_tup_upd, n_tup_del, \
+         n_tup_hot_upd, n_live_tup, n_dead_tup FROM pg_stat_all_tables \
+         WHERE ($1 = '' OR relname ILIKE '%' || $1 || '%' OR schemaname ILIKE '%' || $1 || '%') \
+         ORDER BY {} DESC NULLS LAST LIMIT 200",
+        sort_column
+    );
+    let rows = client.query(&sql, &[&filter]).await?;
+    Ok(rows
+        .iter()
+        .map(|row| {
+            vec![
+                row.get::<_, &str>("schemaname").to_string(),
+                row.get::<_, &str>("relname").to_string(),
+                row.get::<_, Option<i64>>("idx_tup_fetch").unwrap_or(0).to_string(),
+                row.get::<_, Option<i64>>("n_tup_ins").unwrap_or(0).to_string(),
+                row.get::<_, Option<i64>>("n_tup_upd").unwrap_or(0).to_string(),
+                row.get::<_, Option<i64>>("n_tup_del").unwrap_or(0).to_string(),
+                row.get::<_, Option<i64>>("n_tup_hot_upd").unwrap_or(0).to_string(),
+                row.get::<_, Option<i64>>("n_live_tup").unwrap_or(0).to_string(),
+                row.get::<_, Option<i64>>("n_dead_tup").unwrap_or(0).to_string(),
+            ]
+        })
+        .collect())
+}
+
+// Fetch sessions in any state, excluding the backend that runs this query.
+pub async fn fetch_activity(pool: &Pool) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    let client = pool.get().await?;
+    let rows = client
+        .query(
+            "SELECT pid, usename, state, wait_event, query FROM pg_stat_activity \
+             WHERE pid <> pg_backend_pid() ORDER BY state_change DESC NULLS LAST LIMIT 200",
+            &[],
+        )
+        .await?;
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let stat = ActivityStat {
+                pid: row.get("pid"),
+                usename: row.get("usename"),
+                state: row.get("state"),
+                wait_event: row.get("wait_event"),
+                query: row.get("query"),
+            };
+            vec![
+                stat.pid.to_string(),
+                stat.usename.unwrap_or_default(),
+                stat.state.unwrap_or_default(),
+                stat.wait_event.unwrap_or_default(),
+                stat.query.unwrap_or_default(),
+            ]
+        })
+        .collect())
+}
+
+// Fetch per-database counters and derive the buffer cache hit ratio.
+pub async fn fetch_database(pool: &Pool) -> Result<Vec<Vec<String>>, Box<dyn Error>> {
+    let client = pool.get().await?;
+    let rows = client
+        .query(
+            "SELECT datname, xact_commit, xact_rollback, blks_read, blks_hit \
+             FROM pg_stat_database WHERE datname IS NOT NULL ORDER BY xact_commit DESC LIMIT 200",
+            &[],
+        )
+        .await?;
+    Ok(rows
+        .iter()
+        .map(|row| {
+            let stat = DatabaseStat {
+                datname: row.get("datname"),
+                xact_commit: row.get("xact_commit"),
+                xact_rollback: row.get("xact_rollback"),
+                blks_read: row.get("blks_read"),
+                blks_hit: row.get("blks_hit"),
+            };
+            let total = stat.blks_read + stat.blks_hit;
+            let hit_ratio = if total > 0 {
+                100.0 * stat.blks_hit as f64 / total as f64
+            } else {
+                0.0
+            };
+            vec![
+                stat.datname.unwrap_or_default(),
+                stat.xact_commit.to_string(),
+                stat.xact_rollback.to_string(),
+                stat.blks_read.to_string(),
+                stat.blks_hit.to_string(),
+                format!("{:.1}", hit_ratio),
+            ]
+        })
+        .collect())
+}